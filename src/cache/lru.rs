@@ -1,18 +1,45 @@
-use std::collections::VecDeque;
-use std::hash::Hash;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
 use std::rc::Rc;
 
+/// A node in the intrusive doubly-linked list backing `LruCache`.
+///
+/// Nodes live in a `Vec` arena and are linked via indices rather than
+/// pointers so that relinking on access never touches the allocator.
+struct Node<K> {
+    key: Rc<K>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
 /// `LruCache` is an internal structure used by NeuroBin for implementing LRU caching.
 ///
-/// This structure manages the caching of keys using a Least Recently Used (LRU) strategy.
-/// It is designed for internal use within the NeuroBin library and not exposed in the public API.
-pub(crate) struct LruCache<K> {
-    order: VecDeque<Rc<K>>,
-    capacity: usize,
+/// Entries are stored in a `Vec<Node<K>>` arena and linked into a doubly-linked
+/// list ordered from least- to most-recently-used, with a `HashMap` mapping
+/// each key to its arena slot. This keeps `access`, `evict`, and `remove` all
+/// O(1) regardless of how many entries are resident, unlike a scan over a
+/// `VecDeque`. Freed slots are recycled via a free-list so the arena doesn't
+/// grow unbounded under churn.
+///
+/// This structure is designed for internal use within the NeuroBin library
+/// and not exposed in the public API.
+///
+/// `S` is the `BuildHasher` used by the internal key index, defaulting to
+/// the standard library's `RandomState`; callers needing faster, non-
+/// cryptographic hashing for e.g. integer sequence IDs can plug one in via
+/// [`LruCache::with_hasher`].
+pub(crate) struct LruCache<K, S = RandomState> {
+    nodes: Vec<Node<K>>,
+    index: HashMap<Rc<K>, usize, S>,
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    pub(crate) capacity: usize,
 }
 
 /// Implementation details of the `LruCache` struct.
-impl<K: Hash + Eq + Clone> LruCache<K> {
+impl<K: Hash + Eq + Clone> LruCache<K, RandomState> {
     /// Creates a new LRU cache with the specified capacity.
     ///
     /// Initializes an LRU cache that can hold a maximum of `capacity` items.
@@ -20,29 +47,99 @@ impl<K: Hash + Eq + Clone> LruCache<K> {
     /// # Arguments
     ///
     /// * `capacity` - The maximum number of items the cache can hold.
+    #[allow(dead_code)]
     pub(crate) fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq + Clone, S: BuildHasher> LruCache<K, S> {
+    /// Creates a new LRU cache with the specified capacity and hasher.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of items the cache can hold.
+    /// * `hasher` - The `BuildHasher` used by the internal key index.
+    pub(crate) fn with_hasher(capacity: usize, hasher: S) -> Self {
         Self {
-            order: VecDeque::new(),
+            nodes: Vec::new(),
+            index: HashMap::with_hasher(hasher),
+            free: Vec::new(),
+            head: None,
+            tail: None,
             capacity,
         }
     }
 
+    /// Returns the number of items currently tracked by the cache.
+    #[allow(dead_code)]
+    pub(crate) fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Unlinks the node at `idx` from the list, leaving its own `prev`/`next`
+    /// stale until the caller either relinks or frees it.
+    fn unlink(&mut self, idx: usize) {
+        let prev = self.nodes[idx].prev;
+        let next = self.nodes[idx].next;
+        match prev {
+            Some(p) => self.nodes[p].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(n) => self.nodes[n].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Links the node at `idx` onto the tail of the list (most-recently-used end).
+    fn link_at_tail(&mut self, idx: usize) {
+        self.nodes[idx].prev = self.tail;
+        self.nodes[idx].next = None;
+        match self.tail {
+            Some(t) => self.nodes[t].next = Some(idx),
+            None => self.head = Some(idx),
+        }
+        self.tail = Some(idx);
+    }
+
+    /// Allocates an arena slot for `key`, reusing a freed slot if one is available.
+    fn alloc(&mut self, key: Rc<K>) -> usize {
+        let node = Node {
+            key,
+            prev: None,
+            next: None,
+        };
+        if let Some(idx) = self.free.pop() {
+            self.nodes[idx] = node;
+            idx
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
     /// Accesses an item in the cache, marking it as recently used.
     ///
-    /// Moves the accessed item to the end of the order queue, indicating recent use.
-    /// If the item is not in the cache and the cache is full, the least recently used item
-    /// is removed.
+    /// Moves the accessed item to the most-recently-used end of the list.
+    /// If the item is not in the cache and the cache is full, the least
+    /// recently used item is evicted first.
     ///
     /// # Arguments
     ///
     /// * `key` - The key of the item being accessed.
     pub(crate) fn access(&mut self, key: Rc<K>) {
-        if let Some(pos) = self.order.iter().position(|k| k == &key) {
-            self.order.remove(pos);
-        } else if self.order.len() == self.capacity {
-            self.order.pop_front();
+        if let Some(&idx) = self.index.get(&key) {
+            self.unlink(idx);
+            self.link_at_tail(idx);
+        } else {
+            if self.index.len() == self.capacity {
+                self.evict();
+            }
+            let idx = self.alloc(key.clone());
+            self.index.insert(key, idx);
+            self.link_at_tail(idx);
         }
-        self.order.push_back(key);
     }
 
     /// Evicts the least recently used item from the cache.
@@ -53,7 +150,12 @@ impl<K: Hash + Eq + Clone> LruCache<K> {
     /// # Returns
     /// * `Option<Rc<K>>` - The key of the evicted item, if any.
     pub(crate) fn evict(&mut self) -> Option<Rc<K>> {
-        self.order.pop_front()
+        let idx = self.head?;
+        self.unlink(idx);
+        let key = self.nodes[idx].key.clone();
+        self.index.remove(&key);
+        self.free.push(idx);
+        Some(key)
     }
 
     /// Removes a specific item from the cache based on the key.
@@ -64,8 +166,9 @@ impl<K: Hash + Eq + Clone> LruCache<K> {
     ///
     /// * `key` - A reference to the key of the item to remove.
     pub(crate) fn remove(&mut self, key: &Rc<K>) -> bool {
-        if let Some(pos) = self.order.iter().position(|k| k == key) {
-            self.order.remove(pos);
+        if let Some(idx) = self.index.remove(key) {
+            self.unlink(idx);
+            self.free.push(idx);
             true
         } else {
             false
@@ -81,7 +184,7 @@ mod tests {
     #[test]
     fn test_lru_cache_initialization() {
         let lru: LruCache<i32> = LruCache::new(2);
-        assert_eq!(lru.order.len(), 0);
+        assert_eq!(lru.len(), 0);
         assert_eq!(lru.capacity, 2);
     }
 
@@ -92,9 +195,10 @@ mod tests {
         lru.access(Rc::new(2));
         lru.access(Rc::new(1));
 
-        assert_eq!(lru.order.len(), 2);
-        assert_eq!(*lru.order.front().unwrap(), Rc::new(2));
-        assert_eq!(*lru.order.back().unwrap(), Rc::new(1));
+        assert_eq!(lru.len(), 2);
+        // '2' is now least-recently-used, '1' is most-recently-used.
+        assert_eq!(lru.head.map(|idx| lru.nodes[idx].key.clone()), Some(Rc::new(2)));
+        assert_eq!(lru.tail.map(|idx| lru.nodes[idx].key.clone()), Some(Rc::new(1)));
     }
 
     #[test]
@@ -104,10 +208,10 @@ mod tests {
         lru.access(Rc::new(2));
         lru.access(Rc::new(3)); // This should evict '1'
 
-        assert_eq!(lru.order.len(), 2);
-        assert!(lru.order.contains(&Rc::new(2)));
-        assert!(lru.order.contains(&Rc::new(3)));
-        assert!(!lru.order.contains(&Rc::new(1)));
+        assert_eq!(lru.len(), 2);
+        assert!(lru.index.contains_key(&Rc::new(2)));
+        assert!(lru.index.contains_key(&Rc::new(3)));
+        assert!(!lru.index.contains_key(&Rc::new(1)));
     }
 
     #[test]
@@ -117,7 +221,9 @@ mod tests {
         lru.access(key.clone());
         lru.remove(&key);
 
-        assert!(lru.order.is_empty());
+        assert!(lru.len() == 0);
+        assert!(lru.head.is_none());
+        assert!(lru.tail.is_none());
     }
 
     #[test]
@@ -127,7 +233,7 @@ mod tests {
         lru.access(Rc::new(2));
         lru.access(Rc::new(3));
 
-        assert_eq!(lru.order.len(), 2);
+        assert_eq!(lru.len(), 2);
     }
 
     #[test]
@@ -138,8 +244,8 @@ mod tests {
         lru.access(Rc::new(3));
         lru.access(Rc::new(2));
 
-        assert_eq!(*lru.order.front().unwrap(), Rc::new(1));
-        assert_eq!(*lru.order.back().unwrap(), Rc::new(2));
+        assert_eq!(lru.head.map(|idx| lru.nodes[idx].key.clone()), Some(Rc::new(1)));
+        assert_eq!(lru.tail.map(|idx| lru.nodes[idx].key.clone()), Some(Rc::new(2)));
     }
 
     #[test]
@@ -148,6 +254,32 @@ mod tests {
         let non_existing_key = Rc::new(99);
 
         assert!(!lru.remove(&non_existing_key));
-        assert!(lru.order.is_empty());
+        assert!(lru.len() == 0);
+    }
+
+    #[test]
+    fn test_lru_cache_slot_reuse() {
+        // Evicting and re-inserting should recycle arena slots rather than
+        // growing the arena without bound.
+        let mut lru = LruCache::new(1);
+        lru.access(Rc::new(1));
+        lru.access(Rc::new(2)); // evicts '1', frees its slot
+        lru.access(Rc::new(3)); // evicts '2', reuses a freed slot
+
+        assert_eq!(lru.len(), 1);
+        assert_eq!(lru.nodes.len(), 1);
+        assert!(lru.index.contains_key(&Rc::new(3)));
+    }
+
+    #[test]
+    fn test_lru_cache_with_hasher() {
+        let mut lru = LruCache::with_hasher(2, RandomState::new());
+        lru.access(Rc::new(1));
+        lru.access(Rc::new(2));
+        lru.access(Rc::new(3)); // This should evict '1'
+
+        assert_eq!(lru.len(), 2);
+        assert!(lru.index.contains_key(&Rc::new(2)));
+        assert!(lru.index.contains_key(&Rc::new(3)));
     }
 }
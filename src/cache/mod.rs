@@ -3,64 +3,208 @@ mod storage;
 
 use self::lru::LruCache;
 use self::storage::Storage;
-use ndarray::{ArrayD, ArrayViewD, Dimension, SliceInfoElem};
+use ndarray::{ArrayD, ArrayViewD, ArrayViewMutD, Dimension, SliceInfoElem};
 use num_traits::Zero;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::rc::Rc;
 
 /// The `Cache` struct represents a distributed in-memory cache optimized for LLMs.
 /// It stores multi-dimensional data and uses a Least Recently Used (LRU) eviction policy.
-pub struct Cache<K, T> {
-    map: HashMap<Rc<K>, Storage<T>>,
-    lru: LruCache<K>,
+///
+/// `S` is the `BuildHasher` used by the internal maps, defaulting to the
+/// standard library's `RandomState`; callers who don't need SipHash's DoS
+/// resistance (e.g. integer sequence IDs in an LLM serving layer) can plug
+/// in a faster hasher via [`Cache::with_hasher`].
+pub struct Cache<K, T, S = RandomState> {
+    map: HashMap<Rc<K>, Storage<T>, S>,
+    lru: LruCache<K, S>,
     capacity: usize,
+    max_bytes: Option<usize>,
+    current_bytes: usize,
+    weights: HashMap<Rc<K>, usize, S>,
+    total_weight: usize,
 }
 
-impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero> Cache<K, T> {
+impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero> Cache<K, T, RandomState> {
     /// Creates a new `Cache` with the specified capacity.
     ///
     /// # Arguments
     /// * `capacity` - The maximum number of items the cache can hold.
     pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, RandomState::new())
+    }
+
+    /// Creates a new `Cache` bounded by estimated tensor memory rather than entry count.
+    ///
+    /// Tensor sizes vary wildly in an LLM KV-cache, so a fixed entry count is a poor
+    /// proxy for memory pressure. This constructor instead tracks the estimated byte
+    /// size of all resident values and evicts least-recently-used entries on `set`
+    /// until the incoming value fits under `max_bytes`.
+    ///
+    /// # Arguments
+    /// * `max_bytes` - The maximum total estimated size, in bytes, of resident values.
+    pub fn with_memory_limit(max_bytes: usize) -> Self {
+        Self::with_memory_limit_and_hasher(max_bytes, RandomState::new())
+    }
+}
+
+impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero, S: BuildHasher + Clone> Cache<K, T, S> {
+    /// Creates a new `Cache` with the specified capacity and hasher.
+    ///
+    /// # Arguments
+    /// * `capacity` - The maximum number of items the cache can hold.
+    /// * `hasher` - The `BuildHasher` used by the internal maps.
+    pub fn with_hasher(capacity: usize, hasher: S) -> Self {
         Self {
-            map: HashMap::new(),
-            lru: LruCache::new(capacity),
+            map: HashMap::with_hasher(hasher.clone()),
+            lru: LruCache::with_hasher(capacity, hasher.clone()),
             capacity,
+            max_bytes: None,
+            current_bytes: 0,
+            weights: HashMap::with_hasher(hasher),
+            total_weight: 0,
+        }
+    }
+
+    /// Creates a new memory-limited `Cache` with the specified hasher.
+    ///
+    /// See [`Cache::with_memory_limit`] for the eviction semantics.
+    ///
+    /// # Arguments
+    /// * `max_bytes` - The maximum total estimated size, in bytes, of resident values.
+    /// * `hasher` - The `BuildHasher` used by the internal maps.
+    pub fn with_memory_limit_and_hasher(max_bytes: usize, hasher: S) -> Self {
+        Self {
+            map: HashMap::with_hasher(hasher.clone()),
+            lru: LruCache::with_hasher(usize::MAX, hasher.clone()),
+            capacity: usize::MAX,
+            max_bytes: Some(max_bytes),
+            current_bytes: 0,
+            weights: HashMap::with_hasher(hasher),
+            total_weight: 0,
+        }
+    }
+
+    /// Estimates the byte footprint of a single resident entry: the tensor's
+    /// data plus the key's own footprint.
+    fn entry_bytes(storage: &Storage<T>) -> usize {
+        storage.byte_size() + std::mem::size_of::<K>()
+    }
+
+    /// Inserts a key-value pair into the cache with the default weight of `0`.
+    ///
+    /// See [`Cache::set_with_weight`] for the full eviction semantics; this
+    /// is a thin wrapper that leaves entries unweighted, i.e. bounded purely
+    /// by entry count (or byte budget, in memory-limit mode).
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the insertion is successful.
+    /// * `Err(&'static str)` - If the cache can't evict enough to fit the value,
+    ///   or the value alone exceeds the memory limit.
+    pub fn set(&mut self, key: K, value: ArrayD<T>) -> Result<(), &'static str> {
+        if let Some(max_bytes) = self.max_bytes {
+            let incoming_bytes = value.len() * std::mem::size_of::<T>() + std::mem::size_of::<K>();
+            if incoming_bytes > max_bytes {
+                return Err("value exceeds memory limit");
+            }
         }
+        self.set_with_weight(key, value, 0)
+            .map_err(|_| "Cache is full and unable to evict items")
     }
 
-    /// Inserts a key-value pair into the cache.
+    /// Inserts a key-value pair into the cache with an explicit weight.
+    ///
+    /// The cache enforces `number_of_entries + total_weight <= capacity`, so a
+    /// caller can bound the cache by something more meaningful than raw entry
+    /// count alone, e.g. token count or recomputation cost of a cached prefix.
+    /// Entries inserted via [`Cache::set`] default to weight `0` and are bounded
+    /// purely by count. In memory-limit mode the byte budget is enforced in
+    /// addition to the weight bound.
     ///
-    /// If the cache has reached its capacity, the least recently used item is evicted
-    /// before inserting the new key-value pair. This ensures that the cache size
-    /// remains within the defined limits.
+    /// Least-recently-used entries are evicted one at a time until the new
+    /// entry fits. If it still doesn't fit once the cache is empty, the
+    /// key and value are handed back to the caller.
     ///
     /// # Arguments
     /// * `key` - The key associated with the value.
     /// * `value` - The value to store in the cache.
+    /// * `weight` - The caller-assigned weight of this entry.
     ///
     /// # Returns
     /// * `Ok(())` - If the insertion is successful.
-    /// * `Err(&'static str)` - If the cache is full and unable to evict items, or if
-    ///                         any other error occurs.
-    pub fn set(&mut self, key: K, value: ArrayD<T>) -> Result<(), &'static str> {
-        let rc_key = Rc::new(key);
+    /// * `Err((K, ArrayD<T>))` - The rejected key and value, if the entry cannot
+    ///   fit even in an empty cache.
+    pub fn set_with_weight(
+        &mut self,
+        key: K,
+        value: ArrayD<T>,
+        weight: usize,
+    ) -> Result<(), (K, ArrayD<T>)> {
         let shape = value.raw_dim();
         let mut storage = Storage::new(shape.slice());
 
         // Create the slice information directly
-        let indices: Vec<SliceInfoElem> = vec![SliceInfoElem::NewAxis; shape.ndim()];
+        let indices: Vec<SliceInfoElem> = vec![
+            SliceInfoElem::Slice {
+                start: 0,
+                end: None,
+                step: 1,
+            };
+            shape.ndim()
+        ];
         storage.set_subarray(&indices, &value);
 
-        if self.map.len() >= self.capacity {
-            // Evict the least recently used item
-            if let Some(evicted_key) = self.lru.evict() {
-                self.map.remove(&evicted_key);
-            } else {
-                return Err("Cache is full and unable to evict items");
+        let incoming_bytes = Self::entry_bytes(&storage);
+        if let Some(max_bytes) = self.max_bytes {
+            if incoming_bytes > max_bytes {
+                return Err((key, value));
+            }
+        }
+
+        // Replacing an existing key must not leak its old byte footprint into
+        // `current_bytes` either. Remove the old entry up front so the fit
+        // check below sees a clean slate and the eviction loop (if it runs)
+        // can't double-subtract this entry's bytes.
+        if let Some(old_storage) = self.map.remove(&key) {
+            if self.max_bytes.is_some() {
+                self.current_bytes -= Self::entry_bytes(&old_storage);
+            }
+        }
+        if let Some(old_weight) = self.weights.remove(&key) {
+            self.total_weight -= old_weight;
+        }
+
+        loop {
+            let fits_capacity = self.map.len() + 1 + self.total_weight + weight <= self.capacity;
+            let fits_bytes = self
+                .max_bytes
+                .is_none_or(|max_bytes| self.current_bytes + incoming_bytes <= max_bytes);
+            if fits_capacity && fits_bytes {
+                break;
+            }
+            match self.lru.evict() {
+                Some(evicted_key) => {
+                    if let Some(evicted) = self.map.remove(&evicted_key) {
+                        if self.max_bytes.is_some() {
+                            self.current_bytes -= Self::entry_bytes(&evicted);
+                        }
+                    }
+                    if let Some(evicted_weight) = self.weights.remove(&evicted_key) {
+                        self.total_weight -= evicted_weight;
+                    }
+                }
+                None => return Err((key, value)),
             }
         }
 
+        let rc_key = Rc::new(key);
+        if self.max_bytes.is_some() {
+            self.current_bytes += incoming_bytes;
+        }
+        self.weights.insert(rc_key.clone(), weight);
+        self.total_weight += weight;
         self.lru.access(rc_key.clone());
         self.map.insert(rc_key, storage);
         Ok(())
@@ -73,7 +217,7 @@ impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero> Cache<K, T> {
     ///
     /// # Returns
     /// * A result containing a reference to the value if found, or an error message if not.
-    pub fn get(&mut self, key: &K) -> Result<ArrayViewD<T>, &'static str> {
+    pub fn get(&mut self, key: &K) -> Result<ArrayViewD<'_, T>, &'static str> {
         let rc_key = Rc::new(key.clone());
         if let Some(storage) = self.map.get(&rc_key) {
             self.lru.access(rc_key);
@@ -83,6 +227,162 @@ impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero> Cache<K, T> {
         }
     }
 
+    /// Retrieves a mutable view of a value associated with the given key, marking
+    /// it as recently used.
+    ///
+    /// This avoids a delete-then-set round trip (which would reallocate the
+    /// whole tensor) when a caller only needs to write through an existing slot,
+    /// e.g. updating a KV-cache entry in place.
+    ///
+    /// # Arguments
+    /// * `key` - The key for which to retrieve a mutable view.
+    ///
+    /// # Returns
+    /// * A result containing a mutable view of the value if found, or an error message if not.
+    pub fn get_mut(&mut self, key: &K) -> Result<ArrayViewMutD<'_, T>, &'static str> {
+        let rc_key = Rc::new(key.clone());
+        if self.map.contains_key(&rc_key) {
+            self.lru.access(rc_key.clone());
+            Ok(self.map.get_mut(&rc_key).unwrap().get_data_mut())
+        } else {
+            Err("Key not found in cache")
+        }
+    }
+
+    /// Mutates a resident tensor in place via a caller-supplied closure, marking
+    /// it as recently used.
+    ///
+    /// In memory-limit mode, the entry's byte accounting is recomputed after
+    /// the mutation and eviction is re-checked, since the closure may have
+    /// grown the tensor. If the grown entry still doesn't fit once there's
+    /// nothing left to evict, the mutation is rolled back so the call is a
+    /// no-op from the caller's perspective, same as a rejected `set`.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the entry to mutate.
+    /// * `f` - A closure that mutates the stored tensor in place.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the mutation is applied successfully.
+    /// * `Err(&'static str)` - If the key is not found, or the mutated value
+    ///   no longer fits the memory limit (in which case it was rolled back).
+    pub fn mutate(&mut self, key: &K, f: impl FnOnce(&mut ArrayD<T>)) -> Result<(), &'static str> {
+        let rc_key = Rc::new(key.clone());
+        if !self.map.contains_key(&rc_key) {
+            return Err("Key not found in cache");
+        }
+        self.apply_and_reconcile(&rc_key, |storage| {
+            f(storage.data_mut());
+            Ok(())
+        })
+    }
+
+    /// Applies `op` to the resident entry for `rc_key`, then reconciles byte
+    /// accounting against the memory limit.
+    ///
+    /// If `op` grows the entry past the memory limit and eviction can't make
+    /// room for it (e.g. it's the only resident entry), the entry's
+    /// pre-`op` data is restored and its accounting rolled back, so the call
+    /// fails as a no-op rather than leaving a mutated-but-over-budget entry
+    /// behind.
+    fn apply_and_reconcile(
+        &mut self,
+        rc_key: &Rc<K>,
+        op: impl FnOnce(&mut Storage<T>) -> Result<(), &'static str>,
+    ) -> Result<(), &'static str> {
+        let snapshot = self
+            .max_bytes
+            .is_some()
+            .then(|| self.map.get(rc_key).unwrap().get_data().to_owned());
+        let old_bytes = snapshot
+            .is_some()
+            .then(|| Self::entry_bytes(self.map.get(rc_key).unwrap()));
+
+        op(self.map.get_mut(rc_key).unwrap())?;
+        self.lru.access(rc_key.clone());
+
+        if let Some(old_bytes) = old_bytes {
+            let new_bytes = Self::entry_bytes(self.map.get(rc_key).unwrap());
+            if self.max_bytes.is_some_and(|max_bytes| new_bytes > max_bytes) {
+                // The grown entry can't fit even on its own, so roll back
+                // before evicting any other entry on its behalf.
+                *self.map.get_mut(rc_key).unwrap().data_mut() = snapshot.unwrap();
+                return Err("value exceeds memory limit");
+            }
+            self.current_bytes = self.current_bytes + new_bytes - old_bytes;
+            if let Err(err) = self.enforce_memory_limit(rc_key) {
+                *self.map.get_mut(rc_key).unwrap().data_mut() = snapshot.unwrap();
+                self.current_bytes = self.current_bytes + old_bytes - new_bytes;
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends `values` to an existing entry along `axis`, in place.
+    ///
+    /// Autoregressive decoding appends one token's key/value vectors to an
+    /// existing cached tensor along the sequence axis each step; this makes
+    /// that a first-class O(new-data) operation instead of a read-concat-set
+    /// round trip. In memory-limit mode, byte accounting is recomputed and
+    /// eviction re-checked after the append, since the entry has grown. If
+    /// the grown entry still doesn't fit once there's nothing left to evict,
+    /// the append is rolled back so the call is a no-op from the caller's
+    /// perspective, same as a rejected `set`.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the entry to grow.
+    /// * `axis` - The axis along which to append.
+    /// * `values` - The data to append along `axis`.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the append succeeds.
+    /// * `Err(&'static str)` - If the key is not found, `values`' shape is
+    ///   incompatible, or the grown value no longer fits the memory limit
+    ///   (in which case it was rolled back).
+    pub fn append(&mut self, key: &K, axis: usize, values: ArrayViewD<T>) -> Result<(), &'static str> {
+        let rc_key = Rc::new(key.clone());
+        if !self.map.contains_key(&rc_key) {
+            return Err("Key not found in cache");
+        }
+        self.apply_and_reconcile(&rc_key, |storage| storage.append(axis, values))
+    }
+
+    /// Evicts least-recently-used entries (other than `protected`) until
+    /// `current_bytes` is back under the configured memory limit.
+    ///
+    /// No-op when the cache is not in memory-limit mode.
+    fn enforce_memory_limit(&mut self, protected: &Rc<K>) -> Result<(), &'static str> {
+        let max_bytes = match self.max_bytes {
+            Some(max_bytes) => max_bytes,
+            None => return Ok(()),
+        };
+
+        while self.current_bytes > max_bytes {
+            let evicted_key = match self.lru.evict() {
+                Some(evicted_key) => evicted_key,
+                None => break,
+            };
+            if &evicted_key == protected {
+                // Nothing else left to evict; put it back and give up.
+                self.lru.access(evicted_key);
+                break;
+            }
+            if let Some(evicted) = self.map.remove(&evicted_key) {
+                self.current_bytes -= Self::entry_bytes(&evicted);
+            }
+            if let Some(weight) = self.weights.remove(&evicted_key) {
+                self.total_weight -= weight;
+            }
+        }
+
+        if self.current_bytes > max_bytes {
+            Err("value exceeds memory limit")
+        } else {
+            Ok(())
+        }
+    }
+
     /// Deletes a key-value pair from the cache.
     ///
     /// # Arguments
@@ -92,7 +392,13 @@ impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero> Cache<K, T> {
     /// * A result indicating whether the operation was successful.
     pub fn delete(&mut self, key: &K) -> Result<(), &'static str> {
         let rc_key = Rc::new(key.clone());
-        if self.map.remove(&rc_key).is_some() {
+        if let Some(storage) = self.map.remove(&rc_key) {
+            if self.max_bytes.is_some() {
+                self.current_bytes -= Self::entry_bytes(&storage);
+            }
+            if let Some(weight) = self.weights.remove(&rc_key) {
+                self.total_weight -= weight;
+            }
             self.lru.remove(&rc_key);
             Ok(())
         } else {
@@ -104,7 +410,7 @@ impl<K: std::hash::Hash + Eq + Clone, T: Copy + Zero> Cache<K, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ndarray::arr0;
+    use ndarray::{arr0, arr2};
 
     #[test]
     fn test_cache_initialization() {
@@ -149,4 +455,191 @@ mod tests {
         let mut cache: Cache<i32, i32> = Cache::new(2);
         assert_eq!(cache.delete(&1), Err("Key not found in cache"));
     }
+
+    #[test]
+    fn test_cache_memory_limit_eviction() {
+        // Each i32 scalar costs size_of::<i32>() for the tensor plus
+        // size_of::<i32>() for the key, so a 2-entry budget fits exactly 2.
+        let entry_cost = std::mem::size_of::<i32>() * 2;
+        let mut cache: Cache<i32, i32> = Cache::with_memory_limit(entry_cost * 2);
+        let value1 = arr0(42).into_dyn();
+        let value2 = arr0(43).into_dyn();
+        let value3 = arr0(44).into_dyn();
+        assert_eq!(cache.set(1, value1), Ok(()));
+        assert_eq!(cache.set(2, value2.clone()), Ok(()));
+        // Inserting a third entry must evict the least-recently-used one (key 1).
+        assert_eq!(cache.set(3, value3.clone()), Ok(()));
+        assert_eq!(cache.get(&1), Err("Key not found in cache"));
+        assert_eq!(cache.get(&2), Ok(value2.view()));
+        assert_eq!(cache.get(&3), Ok(value3.view()));
+        assert_eq!(cache.current_bytes, entry_cost * 2);
+    }
+
+    #[test]
+    fn test_cache_memory_limit_overwrite_replaces_bytes() {
+        let entry_cost = std::mem::size_of::<i32>() * 2;
+        let mut cache: Cache<i32, i32> = Cache::with_memory_limit(entry_cost);
+        let value1 = arr0(42).into_dyn();
+        let value2 = arr0(43).into_dyn();
+        assert_eq!(cache.set(1, value1), Ok(()));
+        assert_eq!(cache.set(1, value2.clone()), Ok(()));
+        assert_eq!(cache.current_bytes, entry_cost);
+        assert_eq!(cache.get(&1), Ok(value2.view()));
+    }
+
+    #[test]
+    fn test_cache_memory_limit_rejects_oversized_value() {
+        let mut cache: Cache<i32, i32> = Cache::with_memory_limit(1);
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value), Err("value exceeds memory limit"));
+    }
+
+    #[test]
+    fn test_cache_weighted_eviction() {
+        // capacity 5: entry 1 has weight 3, so inserting entry 2 (weight 0)
+        // fits (1 entry + 3 weight + 1 entry + 0 weight = 5), but inserting
+        // entry 3 afterwards must evict the least-recently-used entry first.
+        let mut cache: Cache<i32, i32> = Cache::new(5);
+        let value1 = arr0(42).into_dyn();
+        let value2 = arr0(43).into_dyn();
+        let value3 = arr0(44).into_dyn();
+        assert_eq!(cache.set_with_weight(1, value1, 3), Ok(()));
+        assert_eq!(cache.set_with_weight(2, value2.clone(), 0), Ok(()));
+        assert_eq!(cache.set_with_weight(3, value3.clone(), 0), Ok(()));
+        assert_eq!(cache.get(&1), Err("Key not found in cache"));
+        assert_eq!(cache.get(&2), Ok(value2.view()));
+        assert_eq!(cache.get(&3), Ok(value3.view()));
+        assert_eq!(cache.total_weight, 0);
+    }
+
+    #[test]
+    fn test_cache_weighted_rejects_when_too_heavy_for_empty_cache() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        let value = arr0(42).into_dyn();
+        let err = cache.set_with_weight(1, value.clone(), 5).unwrap_err();
+        assert_eq!(err, (1, value));
+    }
+
+    #[test]
+    fn test_cache_weighted_overwrite_replaces_weight() {
+        let mut cache: Cache<i32, i32> = Cache::new(100);
+        let value1 = arr0(42).into_dyn();
+        let value2 = arr0(43).into_dyn();
+        assert_eq!(cache.set_with_weight(1, value1, 3), Ok(()));
+        assert_eq!(cache.set_with_weight(1, value2.clone(), 2), Ok(()));
+        assert_eq!(cache.total_weight, 2);
+        assert_eq!(cache.get(&1), Ok(value2.view()));
+    }
+
+    #[test]
+    fn test_cache_default_set_has_zero_weight() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value), Ok(()));
+        assert_eq!(cache.total_weight, 0);
+    }
+
+    #[test]
+    fn test_cache_get_mut() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value), Ok(()));
+        cache.get_mut(&1).unwrap().fill(99);
+        assert_eq!(cache.get(&1), Ok(arr0(99).into_dyn().view()));
+    }
+
+    #[test]
+    fn test_cache_get_mut_missing_key() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        assert_eq!(cache.get_mut(&1).err(), Some("Key not found in cache"));
+    }
+
+    #[test]
+    fn test_cache_mutate() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value), Ok(()));
+        assert_eq!(cache.mutate(&1, |data| data.fill(99)), Ok(()));
+        assert_eq!(cache.get(&1), Ok(arr0(99).into_dyn().view()));
+    }
+
+    #[test]
+    fn test_cache_mutate_missing_key() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        assert_eq!(
+            cache.mutate(&1, |data| data.fill(99)),
+            Err("Key not found in cache")
+        );
+    }
+
+    #[test]
+    fn test_cache_mutate_updates_memory_accounting() {
+        let entry_cost = std::mem::size_of::<i32>() * 2;
+        let mut cache: Cache<i32, i32> = Cache::with_memory_limit(entry_cost);
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value), Ok(()));
+        assert_eq!(cache.mutate(&1, |data| data.fill(99)), Ok(()));
+        assert_eq!(cache.current_bytes, entry_cost);
+    }
+
+    #[test]
+    fn test_cache_mutate_rolls_back_when_over_memory_limit() {
+        let entry_cost = std::mem::size_of::<i32>() * 2;
+        let mut cache: Cache<i32, i32> = Cache::with_memory_limit(entry_cost);
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value), Ok(()));
+
+        // Growing the only resident entry past the limit leaves nothing left
+        // to evict, so the mutation must be rolled back rather than
+        // committed with accounting stuck over budget.
+        let result = cache.mutate(&1, |data| *data = ArrayD::from_elem(vec![4], 7));
+        assert_eq!(result, Err("value exceeds memory limit"));
+        assert_eq!(cache.get(&1), Ok(arr0(42).into_dyn().view()));
+        assert_eq!(cache.current_bytes, entry_cost);
+    }
+
+    #[test]
+    fn test_cache_append() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        let value = arr2(&[[1, 2]]).into_dyn();
+        assert_eq!(cache.set(1, value), Ok(()));
+        let appended = arr2(&[[3, 4]]).into_dyn();
+        assert_eq!(cache.append(&1, 0, appended.view()), Ok(()));
+        assert_eq!(cache.get(&1), Ok(arr2(&[[1, 2], [3, 4]]).into_dyn().view()));
+    }
+
+    #[test]
+    fn test_cache_append_missing_key() {
+        let mut cache: Cache<i32, i32> = Cache::new(2);
+        let appended = arr2(&[[3, 4]]).into_dyn();
+        assert_eq!(
+            cache.append(&1, 0, appended.view()),
+            Err("Key not found in cache")
+        );
+    }
+
+    #[test]
+    fn test_cache_append_rolls_back_when_over_memory_limit() {
+        let entry_cost = std::mem::size_of::<i32>() * 3; // key + 2 elems
+        let mut cache: Cache<i32, i32> = Cache::with_memory_limit(entry_cost);
+        let value = arr2(&[[1, 2]]).into_dyn();
+        assert_eq!(cache.set(1, value.clone()), Ok(()));
+
+        // Appending grows the only resident entry past the limit; with
+        // nothing else to evict, the growth must be rolled back.
+        let appended = arr2(&[[3, 4], [5, 6]]).into_dyn();
+        let result = cache.append(&1, 0, appended.view());
+        assert_eq!(result, Err("value exceeds memory limit"));
+        assert_eq!(cache.get(&1), Ok(value.view()));
+        assert_eq!(cache.current_bytes, entry_cost);
+    }
+
+    #[test]
+    fn test_cache_with_hasher() {
+        let mut cache: Cache<i32, i32, RandomState> =
+            Cache::with_hasher(2, RandomState::new());
+        let value = arr0(42).into_dyn();
+        assert_eq!(cache.set(1, value.clone()), Ok(()));
+        assert_eq!(cache.get(&1), Ok(value.view()));
+    }
 }
@@ -1,4 +1,4 @@
-use ndarray::{ArrayD, ArrayViewD, SliceInfoElem};
+use ndarray::{ArrayD, ArrayViewD, ArrayViewMutD, SliceInfoElem};
 use num_traits::Zero;
 
 // Internal Storage struct for NeuroBin
@@ -8,6 +8,12 @@ use num_traits::Zero;
 // within the NeuroBin library.
 pub(crate) struct Storage<T> {
     data: ArrayD<T>,
+    // The axis most recently grown via `append`, and the logical (in-use)
+    // length along it. `data` may extend past this length on that axis —
+    // spare capacity reserved so a run of appends along the same axis
+    // amortizes to O(1) rather than reallocating on every call, the same
+    // way `Vec` over-allocates on push. `None` until the first `append`.
+    grown_axis: Option<(usize, usize)>,
 }
 
 // Implementation details of the `Storage` struct.
@@ -23,9 +29,46 @@ impl<T: Copy + Zero> Storage<T> {
     pub(crate) fn new(shape: &[usize]) -> Self {
         Self {
             data: ArrayD::zeros(shape.to_owned()),
+            grown_axis: None,
         }
     }
 
+    // The logical (in-use) shape, i.e. `data`'s shape with any spare
+    // capacity reserved by `append` trimmed back off.
+    fn logical_shape(&self) -> Vec<usize> {
+        let mut shape = self.data.shape().to_vec();
+        if let Some((axis, len)) = self.grown_axis {
+            shape[axis] = len;
+        }
+        shape
+    }
+
+    // A full-range `Slice` index for every axis of `shape`, used to carve the
+    // logical region out of (or into) a possibly larger backing array.
+    fn full_range_indices(shape: &[usize]) -> Vec<SliceInfoElem> {
+        shape
+            .iter()
+            .map(|&dim| SliceInfoElem::Slice {
+                start: 0,
+                end: Some(dim as isize),
+                step: 1,
+            })
+            .collect()
+    }
+
+    // Drops any spare capacity reserved by `append`, reallocating `data` down
+    // to exactly its logical shape. Called before handing out a `&mut ArrayD`
+    // that a caller might reshape out from under the reserved capacity.
+    fn compact(&mut self) {
+        if self.grown_axis.is_none() {
+            return;
+        }
+        let logical_shape = self.logical_shape();
+        let region = Self::full_range_indices(&logical_shape);
+        self.data = self.data.slice(region.as_slice()).to_owned();
+        self.grown_axis = None;
+    }
+
     // Sets a sub-array within the `Storage`.
     //
     // This function allows setting a specific part of the storage using given
@@ -52,8 +95,130 @@ impl<T: Copy + Zero> Storage<T> {
     //
     // * `ArrayViewD<T>` - A view of the entire multi-dimensional data array,
     //                     allowing read-only access to its elements.
-    pub(crate) fn get_data(&self) -> ArrayViewD<T> {
-        self.data.view()
+    pub(crate) fn get_data(&self) -> ArrayViewD<'_, T> {
+        match self.grown_axis {
+            Some(_) => self.data.slice(Self::full_range_indices(&self.logical_shape()).as_slice()),
+            None => self.data.view(),
+        }
+    }
+
+    // Retrieves a mutable view of the entire data stored in `Storage`.
+    //
+    // This function provides in-place write access to the underlying data,
+    // avoiding the delete-then-set round trip that would otherwise be needed
+    // to update a resident tensor.
+    //
+    // # Returns
+    //
+    // * `ArrayViewMutD<T>` - A mutable view of the entire multi-dimensional
+    //                        data array.
+    pub(crate) fn get_data_mut(&mut self) -> ArrayViewMutD<'_, T> {
+        match self.grown_axis {
+            Some(_) => {
+                let region = Self::full_range_indices(&self.logical_shape());
+                self.data.slice_mut(region.as_slice())
+            }
+            None => self.data.view_mut(),
+        }
+    }
+
+    // Retrieves a mutable reference to the underlying `ArrayD`.
+    //
+    // Used by callers that need to replace the array itself, e.g. reshaping
+    // it in place. Any spare capacity reserved by `append` is dropped first,
+    // since a caller here is free to change the array's shape out from under
+    // it.
+    //
+    // # Returns
+    //
+    // * `&mut ArrayD<T>` - A mutable reference to the stored array.
+    pub(crate) fn data_mut(&mut self) -> &mut ArrayD<T> {
+        self.compact();
+        &mut self.data
+    }
+
+    // Grows the storage along `axis`, writing `values` into the newly created slice.
+    //
+    // This lets callers append along a sequence axis (as in autoregressive
+    // KV-cache growth) without reading the whole tensor out, concatenating,
+    // and re-setting it. All dimensions other than `axis` must match the
+    // existing shape. Growth is amortized Vec-style: when the backing array
+    // has to be reallocated, it reserves spare capacity along `axis` (at
+    // least double what it held), so a run of appends along the same axis
+    // only reallocates O(log n) times rather than on every call.
+    //
+    // # Arguments
+    //
+    // * `axis`   - The axis along which to grow the storage.
+    // * `values` - The data to append along `axis`.
+    //
+    // # Returns
+    //
+    // * `Ok(())` on success, or `Err(&'static str)` if `axis` is out of
+    //   bounds, `values` has a different number of dimensions, or disagrees
+    //   with the existing shape on any axis other than `axis`.
+    pub(crate) fn append(&mut self, axis: usize, values: ArrayViewD<T>) -> Result<(), &'static str> {
+        let old_shape = self.logical_shape();
+        if axis >= old_shape.len() {
+            return Err("append axis out of bounds");
+        }
+        let new_values_shape = values.shape();
+        if new_values_shape.len() != old_shape.len() {
+            return Err("append values have a different number of dimensions");
+        }
+        for (i, (&old_dim, &new_dim)) in old_shape.iter().zip(new_values_shape.iter()).enumerate() {
+            if i != axis && old_dim != new_dim {
+                return Err("append values disagree with existing shape on a non-append axis");
+            }
+        }
+
+        let append_len = new_values_shape[axis];
+        let new_logical_len = old_shape[axis] + append_len;
+
+        let existing_capacity = if self.grown_axis.map(|(a, _)| a) == Some(axis) {
+            self.data.shape()[axis]
+        } else {
+            old_shape[axis]
+        };
+
+        if new_logical_len > existing_capacity {
+            // No room left on `axis`; reallocate with amortized spare
+            // capacity rather than growing to exactly fit this one append.
+            let grown_capacity = (existing_capacity.max(1) * 2).max(new_logical_len);
+            let mut new_shape = old_shape.clone();
+            new_shape[axis] = grown_capacity;
+            let mut new_data = ArrayD::zeros(new_shape);
+
+            let existing_region = Self::full_range_indices(&old_shape);
+            new_data
+                .slice_mut(existing_region.as_slice())
+                .assign(&self.data.slice(existing_region.as_slice()));
+            self.data = new_data;
+        }
+
+        let appended_region: Vec<SliceInfoElem> = old_shape
+            .iter()
+            .enumerate()
+            .map(|(i, &dim)| {
+                if i == axis {
+                    SliceInfoElem::Slice {
+                        start: dim as isize,
+                        end: Some(new_logical_len as isize),
+                        step: 1,
+                    }
+                } else {
+                    SliceInfoElem::Slice {
+                        start: 0,
+                        end: Some(dim as isize),
+                        step: 1,
+                    }
+                }
+            })
+            .collect();
+        self.data.slice_mut(appended_region.as_slice()).assign(&values);
+
+        self.grown_axis = Some((axis, new_logical_len));
+        Ok(())
     }
 
     // Retrieves a sub-array from `Storage`.
@@ -70,9 +235,21 @@ impl<T: Copy + Zero> Storage<T> {
     //
     // A view (`ArrayViewD`) of the sub-array.
     #[allow(dead_code)]
-    pub(crate) fn get_subarray(&self, indices: &[SliceInfoElem]) -> ArrayViewD<T> {
+    pub(crate) fn get_subarray(&self, indices: &[SliceInfoElem]) -> ArrayViewD<'_, T> {
         self.data.slice(indices)
     }
+
+    // Estimates the number of bytes occupied by the stored tensor.
+    //
+    // This is the element count times `size_of::<T>()`; it does not include
+    // any fixed overhead for the `Storage` struct itself.
+    //
+    // # Returns
+    //
+    // * `usize` - The estimated size, in bytes, of the underlying data.
+    pub(crate) fn byte_size(&self) -> usize {
+        self.logical_shape().iter().product::<usize>() * std::mem::size_of::<T>()
+    }
 }
 
 // Unit tests for the `Storage` struct.
@@ -145,4 +322,84 @@ mod tests {
         let retrieved_values = storage.get_subarray(&indices);
         assert_eq!(retrieved_values, values_dyn.view());
     }
+
+    /// Test for `byte_size` function.
+    #[test]
+    fn test_byte_size() {
+        let storage: Storage<i32> = Storage::new(&[2, 3]);
+        assert_eq!(storage.byte_size(), 6 * std::mem::size_of::<i32>());
+    }
+
+    /// Test for `get_data_mut` function.
+    #[test]
+    fn test_get_data_mut() {
+        let mut storage: Storage<i32> = Storage::new(&[2, 2]);
+        storage.get_data_mut().fill(7);
+        assert_eq!(storage.data, ArrayD::from_elem(vec![2, 2], 7));
+    }
+
+    /// Test for `append` function.
+    #[test]
+    fn test_append() {
+        let mut storage: Storage<i32> = Storage::new(&[1, 2]);
+        let indices = vec![
+            SliceInfoElem::Slice {
+                start: 0,
+                end: None,
+                step: 1
+            };
+            2
+        ];
+        storage.set_subarray(&indices, &arr2(&[[1, 2]]).into_dyn());
+
+        storage.append(0, arr2(&[[3, 4]]).into_dyn().view()).unwrap();
+
+        assert_eq!(storage.data, arr2(&[[1, 2], [3, 4]]).into_dyn());
+    }
+
+    /// Test for `append` rejecting a shape mismatch on a non-append axis.
+    #[test]
+    fn test_append_shape_mismatch() {
+        let mut storage: Storage<i32> = Storage::new(&[1, 2]);
+        let result = storage.append(0, arr2(&[[1, 2, 3]]).into_dyn().view());
+        assert_eq!(
+            result,
+            Err("append values disagree with existing shape on a non-append axis")
+        );
+    }
+
+    /// Test for `append` rejecting an out-of-bounds axis.
+    #[test]
+    fn test_append_axis_out_of_bounds() {
+        let mut storage: Storage<i32> = Storage::new(&[1, 2]);
+        let result = storage.append(5, arr2(&[[1, 2]]).into_dyn().view());
+        assert_eq!(result, Err("append axis out of bounds"));
+    }
+
+    /// Repeated appends along the same axis should reuse reserved capacity
+    /// rather than reallocating on every call.
+    #[test]
+    fn test_append_amortized_growth_reuses_capacity() {
+        let mut storage: Storage<i32> = Storage::new(&[0, 2]);
+        storage.append(0, arr2(&[[1, 2]]).into_dyn().view()).unwrap();
+        let capacity_after_growth = storage.data.shape()[0];
+        assert!(capacity_after_growth > 1, "expected spare capacity to be reserved");
+
+        storage.append(0, arr2(&[[3, 4]]).into_dyn().view()).unwrap();
+        // Still within the previously reserved capacity, so no reallocation.
+        assert_eq!(storage.data.shape()[0], capacity_after_growth);
+        assert_eq!(storage.get_data(), arr2(&[[1, 2], [3, 4]]).into_dyn().view());
+        assert_eq!(storage.byte_size(), 4 * std::mem::size_of::<i32>());
+    }
+
+    /// `data_mut` must drop any spare append capacity before handing out the
+    /// raw array, so a caller that reshapes it doesn't see stale slack.
+    #[test]
+    fn test_data_mut_compacts_spare_capacity() {
+        let mut storage: Storage<i32> = Storage::new(&[0, 2]);
+        storage.append(0, arr2(&[[1, 2]]).into_dyn().view()).unwrap();
+        assert!(storage.data.shape()[0] > 1);
+
+        assert_eq!(storage.data_mut().shape(), &[1, 2]);
+    }
 }